@@ -13,7 +13,7 @@ fn bench_constructor(c: &mut Criterion) {
 }
 
 fn bench_generate_by_wam_next(c: &mut Criterion) {
-    let builder = WalkerTableBuilder::new(&WEIGHTS);
+    let builder = WalkerTableBuilder::new(&WEIGHTS).unwrap();
     let table = builder.build();
 
     let mut result = [0; 100_000];
@@ -28,7 +28,7 @@ fn bench_generate_by_wam_next(c: &mut Criterion) {
 }
 
 fn bench_generate_by_wam_next_rng(c: &mut Criterion) {
-    let builder = WalkerTableBuilder::new(&WEIGHTS);
+    let builder = WalkerTableBuilder::new(&WEIGHTS).unwrap();
     let table = builder.build();
 
     let mut rng = rand::thread_rng();
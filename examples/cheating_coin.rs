@@ -6,7 +6,7 @@ fn main() {
     let cheating_coin = ["Heads!", "Tails!"];
     let index_weights = [0.55, 0.45];
 
-    let builder = WalkerTableBuilder::new(&index_weights);
+    let builder = WalkerTableBuilder::new(&index_weights).unwrap();
     let wa_table = builder.build();
 
     // If you want to process something in a large number of
@@ -1,14 +1,18 @@
 //! Builds a [`WalkerTable`] instance.
 
+use crate::error::WeightedRandError;
 use crate::table::WalkerTable;
-use crate::util::math::gcd_for_slice;
+use crate::weight::AliasableWeight;
 
-pub trait NewBuilder<T> {
-    /// Creates a new instance of [`WalkerTableBuilder`] from
-    /// [`&[u32]`] or [`&[f32]`].
+pub trait NewBuilder<T: AliasableWeight> {
+    /// Creates a new instance of [`WalkerTableBuilder`] from a slice of
+    /// weights, e.g. [`&[u32]`] or [`&[f32]`].
     ///
-    /// Values less than 0 will be calculated as 0.
-    fn new(index_weights: &[T]) -> WalkerTableBuilder;
+    /// Returns [`WeightedRandError`] if any weight is negative, `NaN`, or
+    /// infinite, or if the weights sum to 0.
+    fn new(index_weights: &[T]) -> Result<Self, WeightedRandError>
+    where
+        Self: Sized;
 }
 
 /// Builder of [`WalkerTable`]
@@ -19,8 +23,8 @@ pub trait NewBuilder<T> {
 /// use weighted_rand::builder::*;
 ///
 /// fn main() {
-///     let index_weights = [1, 2, 3, 4];
-///     let builder = WalkerTableBuilder::new(&index_weights);
+///     let index_weights: [u32; 4] = [1, 2, 3, 4];
+///     let builder = WalkerTableBuilder::new(&index_weights).unwrap();
 ///     let wa_table = builder.build();
 /// }
 /// ```
@@ -38,64 +42,51 @@ pub trait NewBuilder<T> {
 /// for each index are 0.2, 0.1, 0.7 and 0. If a weight value is 0, the
 /// corresponding index will not be output. In other words, the index 3 will
 /// not be output in the this cases.
-pub struct WalkerTableBuilder {
+pub struct WalkerTableBuilder<T: AliasableWeight> {
     /// Weights of the output indexes.
-    index_weights: Vec<u32>,
+    index_weights: Vec<T>,
 }
 
-impl NewBuilder<u32> for WalkerTableBuilder {
-    fn new(index_weights: &[u32]) -> WalkerTableBuilder {
-        let table_len = index_weights.len() as u32;
-
-        // Process that the mean of index_weights does not become a float value
-        let ws = index_weights
-            .iter()
-            .map(|w| w * table_len)
-            .collect::<Vec<u32>>();
-
-        WalkerTableBuilder { index_weights: ws }
-    }
-}
-
-impl NewBuilder<f32> for WalkerTableBuilder {
-    fn new(index_weights: &[f32]) -> WalkerTableBuilder {
-        let ws = index_weights
-            .iter()
-            .map(|w| (w * 10000.0).round() as u32)
-            .collect::<Vec<u32>>();
+impl<T: AliasableWeight> NewBuilder<T> for WalkerTableBuilder<T> {
+    fn new(index_weights: &[T]) -> Result<WalkerTableBuilder<T>, WeightedRandError> {
+        for w in index_weights {
+            w.validate()?;
+        }
 
-        let gcd = gcd_for_slice(&ws);
-        let ws = ws.iter().map(|w| w / gcd).collect::<Vec<u32>>();
+        let sum = index_weights.iter().map(|w| w.as_f64()).sum::<f64>();
+        if sum == 0.0 {
+            return Err(WeightedRandError::SumWeights);
+        }
 
-        WalkerTableBuilder::new(&ws)
+        Ok(WalkerTableBuilder {
+            index_weights: index_weights.to_vec(),
+        })
     }
 }
 
-impl WalkerTableBuilder {
+impl<T: AliasableWeight> WalkerTableBuilder<T> {
     /// Builds a new instance of [`WalkerTable`].
     pub fn build(&self) -> WalkerTable {
-        let table_len = self.index_weights.len();
-
-        if self.sum() == 0 {
-            // Returns WalkerTable that performs unweighted random sampling.
-            return WalkerTable::new(vec![0; table_len], vec![0.0; table_len]);
-        }
-
         let (aliases, probs) = self.calc_table();
 
         WalkerTable::new(aliases, probs)
     }
 
     /// Inverses given weights
-    pub fn inverse(self) -> WalkerTableBuilder {
-        let min_value = match self.index_weights.iter().min() {
-            Some(v) => *v,
-            None => 0,
-        };
-        let max_value = match self.index_weights.iter().max() {
-            Some(v) => *v,
-            None => 0,
-        };
+    pub fn inverse(self) -> WalkerTableBuilder<T> {
+        // `T` is only `PartialOrd` (not `Ord`, since floats aren't), so
+        // `Iterator::min`/`max` aren't available here.
+        let first = self.index_weights[0];
+        let min_value = self
+            .index_weights
+            .iter()
+            .copied()
+            .fold(first, |acc, cur| if cur < acc { cur } else { acc });
+        let max_value = self
+            .index_weights
+            .iter()
+            .copied()
+            .fold(first, |acc, cur| if cur > acc { cur } else { acc });
         Self {
             index_weights: self
                 .index_weights
@@ -113,89 +104,94 @@ impl WalkerTableBuilder {
         }
     }
 
-    /// Calculates the sum of `index_weights`.
-    fn sum(&self) -> u32 {
-        self.index_weights.iter().fold(0, |acc, cur| acc + cur)
-    }
-
-    /// Calculates the mean of `index_weights`.
-    fn mean(&self) -> u32 {
-        self.sum() / self.index_weights.len() as u32
-    }
-
-    /// Returns the tables of aliases and probabilities.
+    /// Returns the tables of aliases and probabilities, built directly in
+    /// floating point with Vose's alias method.
+    ///
+    /// Each weight is scaled so the mean becomes 1
+    /// (`scaled[i] = w[i] * n / total`), then indexes are partitioned into
+    /// "small" (`scaled < 1`) and "large" (`scaled >= 1`) worklists. Popping
+    /// one of each pairs them: the small index's probability of aliasing
+    /// away is `1 - scaled[small]`, the large index absorbs the
+    /// difference and is re-filed into whichever worklist it now belongs
+    /// to. Unlike scaling weights by the table length, this can't overflow
+    /// regardless of `T`, and unlike quantizing to a fixed denominator, it
+    /// keeps full `f32`/`f64` precision.
     fn calc_table(&self) -> (Vec<usize>, Vec<f32>) {
-        let table_len = self.index_weights.len();
-        let (mut below_vec, mut above_vec) = self.separate_weight();
-        let mean = self.mean();
-
-        let mut aliases = vec![0; table_len];
-        let mut probs = vec![0.0; table_len];
-        loop {
-            match below_vec.pop() {
-                Some(below) => {
-                    if let Some(above) = above_vec.pop() {
-                        let diff = mean - below.1;
-                        aliases[below.0] = above.0 as usize;
-                        probs[below.0] = diff as f32 / mean as f32;
-                        if above.1 - diff <= mean {
-                            below_vec.push((above.0, above.1 - diff));
-                        } else {
-                            above_vec.push((above.0, above.1 - diff));
-                        }
-                    } else {
-                        aliases[below.0] = below.0 as usize;
-                        probs[below.0] = below.1 as f32 / mean as f32;
-                    }
-                }
-                None => break,
+        let n = self.index_weights.len();
+        let total = self
+            .index_weights
+            .iter()
+            .fold(0.0, |acc, w| acc + w.as_f64());
+
+        let mut scaled = self
+            .index_weights
+            .iter()
+            .map(|w| w.as_f64() * n as f64 / total)
+            .collect::<Vec<f64>>();
+
+        let mut small = Vec::with_capacity(n);
+        let mut large = Vec::with_capacity(n);
+        for (i, s) in scaled.iter().enumerate() {
+            if *s < 1.0 {
+                small.push(i);
+            } else {
+                large.push(i);
             }
         }
 
-        (aliases, probs)
-    }
-
-    /// Divide the values of `index_weights` based on the mean of them.
-    ///
-    /// The tail value is a weight and head is its index.
-    fn separate_weight(&self) -> (Vec<(usize, u32)>, Vec<(usize, u32)>) {
-        let mut below_vec = Vec::with_capacity(self.index_weights.len());
-        let mut above_vec = Vec::with_capacity(self.index_weights.len());
-        for (i, w) in self.index_weights.iter().enumerate() {
-            if *w <= self.mean() {
-                below_vec.push((i, *w));
+        let mut aliases = vec![0; n];
+        let mut probs = vec![0.0; n];
+        while let Some(s) = small.pop() {
+            if let Some(l) = large.pop() {
+                let prob_away = 1.0 - scaled[s];
+                probs[s] = prob_away as f32;
+                aliases[s] = l;
+
+                scaled[l] -= prob_away;
+                if scaled[l] < 1.0 {
+                    small.push(l);
+                } else {
+                    large.push(l);
+                }
             } else {
-                above_vec.push((i, *w));
+                aliases[s] = s;
             }
         }
-        (below_vec, above_vec)
+        // Floating-point rounding can strand a handful of indexes here
+        // with `scaled` ~= 1; they simply never alias away.
+        for l in large {
+            aliases[l] = l;
+        }
+
+        (aliases, probs)
     }
 }
 
 #[cfg(test)]
 mod builder_test {
     use crate::builder::*;
+    use crate::error::WeightedRandError;
     use crate::table::WalkerTable;
 
     #[test]
     fn make_table_from_u32() {
-        let index_weights = [2, 7, 9, 2, 4, 8, 1, 3, 6, 5];
-        let builder = WalkerTableBuilder::new(&index_weights);
+        let index_weights: [u32; 10] = [2, 7, 9, 2, 4, 8, 1, 3, 6, 5];
+        let builder = WalkerTableBuilder::new(&index_weights).unwrap();
         let w_table = builder.build();
 
         let expected = WalkerTable::new(
             vec![2, 1, 1, 2, 2, 2, 5, 9, 5, 8],
             vec![
-                0.574468085106383,
-                1.0,
-                0.48936170212766,
-                0.574468085106383,
-                0.148936170212766,
-                0.106382978723404,
-                0.787234042553192,
-                0.361702127659574,
-                0.0212765957446809,
-                0.297872340425532,
+                0.5744681,
+                0.0,
+                0.4893617,
+                0.5744681,
+                0.14893617,
+                0.10638298,
+                0.78723407,
+                0.3617021,
+                0.021276595,
+                0.29787233,
             ],
         );
 
@@ -204,23 +200,28 @@ mod builder_test {
 
     #[test]
     fn make_table_from_f32() {
-        let index_weights = [0.1, 0.2, 0.3, -0.4];
-        let builder = WalkerTableBuilder::new(&index_weights);
+        let index_weights = [1.0, 2.0, 3.0, 4.0];
+        let builder = WalkerTableBuilder::new(&index_weights).unwrap();
         let w_table = builder.build();
 
-        let expected = WalkerTable::new(vec![1, 1, 1, 2], vec![0.333333333333333, 1.0, 0.0, 1.0]);
+        let expected = WalkerTable::new(vec![3, 3, 2, 2], vec![0.6, 0.2, 0.0, 0.2]);
 
         assert_eq!(w_table, expected)
     }
 
     #[test]
-    fn when_sum_is_zero() {
-        let index_weights = [0; 5];
-        let builder = WalkerTableBuilder::new(&index_weights);
-        let w_table = builder.build();
+    fn when_weight_is_negative() {
+        let index_weights = [0.1, 0.2, 0.3, -0.4];
+        let result = WalkerTableBuilder::new(&index_weights);
 
-        let expected = WalkerTable::new(vec![0; 5], vec![0.0; 5]);
+        assert!(matches!(result, Err(WeightedRandError::InvalidWeight)))
+    }
 
-        assert_eq!(w_table, expected)
+    #[test]
+    fn when_sum_is_zero() {
+        let index_weights: [u32; 5] = [0; 5];
+        let result = WalkerTableBuilder::new(&index_weights);
+
+        assert!(matches!(result, Err(WeightedRandError::SumWeights)))
     }
 }
@@ -4,12 +4,16 @@ use std::fmt;
 #[derive(Debug)]
 pub enum WeightedRandError {
     SumWeights,
+    InvalidWeight,
 }
 
 impl fmt::Display for WeightedRandError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             WeightedRandError::SumWeights => write!(f, "Sum of weights is 0."),
+            WeightedRandError::InvalidWeight => {
+                write!(f, "A weight is negative, NaN, or infinite.")
+            }
         }
     }
 }
@@ -18,12 +22,14 @@ impl error::Error for WeightedRandError {
     fn description(&self) -> &str {
         match *self {
             WeightedRandError::SumWeights => "Sum of weights is 0.",
+            WeightedRandError::InvalidWeight => "A weight is negative, NaN, or infinite.",
         }
     }
 
     fn cause(&self) -> Option<&dyn error::Error> {
         match *self {
             WeightedRandError::SumWeights => None,
+            WeightedRandError::InvalidWeight => None,
         }
     }
 }
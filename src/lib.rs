@@ -29,9 +29,9 @@
 //!     // In the following case, the ratio of each weight
 //!     // is "2 : 1 : 7 : 0", and the output probabilities
 //!     // for each index are 0.2, 0.1, 0.7 and 0.
-//!     let index_weights = [2, 1, 7, 0];
+//!     let index_weights: [u32; 4] = [2, 1, 7, 0];
 //!
-//!     let builder = WalkerTableBuilder::new(&index_weights);
+//!     let builder = WalkerTableBuilder::new(&index_weights).unwrap();
 //!     let wa_table = builder.build();
 //!
 //!     for i in (0..10).map(|_| wa_table.next()) {
@@ -51,7 +51,7 @@
 //!     let cheating_coin = ["Heads!", "Tails!"];
 //!     let index_weights = [0.55, 0.45];
 //!
-//!     let builder = WalkerTableBuilder::new(&index_weights);
+//!     let builder = WalkerTableBuilder::new(&index_weights).unwrap();
 //!     let wa_table = builder.build();
 //!
 //!     // If you want to process something in a large number of
@@ -70,6 +70,6 @@
 //!
 
 pub mod builder;
+pub mod error;
 pub mod table;
-
-mod util;
+pub mod weight;
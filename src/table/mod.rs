@@ -1,8 +1,13 @@
 //! Weighted random index generator by Walker's Alias Method.
 
+mod weighted_shuffle;
+
+use rand::distributions::Distribution;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
 
+pub use weighted_shuffle::WeightedShuffle;
+
 /// Table of aliases and probabilities
 ///
 /// In Walker's Alias Method, weighted random sampling is performed by the
@@ -41,7 +46,21 @@ impl WalkerTable {
     }
 
     /// Returns an index at random using an external RNG which implements Rng.
+    ///
+    /// This is a thin wrapper around [`Distribution::sample`], kept for
+    /// backward compatibility.
     pub fn next_rng(&self, rng: &mut impl Rng) -> usize {
+        self.sample(rng)
+    }
+}
+
+impl Distribution<usize> for WalkerTable {
+    /// Samples an index at random, weighted by this table's probabilities.
+    ///
+    /// This lets [`WalkerTable`] interoperate with the rest of the `rand`
+    /// ecosystem, e.g. `rng.sample(&table)` or `table.sample_iter(rng)` for
+    /// a lazy, infinite stream of weighted indexes.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
         let i = rng.gen_range(0..self.probs.len());
         let r = rng.gen::<f32>();
         if r < self.probs[i] {
@@ -65,9 +84,9 @@ mod table_test {
     }
 
     #[test]
-    fn unweighted_random_sampling() {
-        let index_weights = [0; 4];
-        let builder = WalkerTableBuilder::new(&index_weights);
+    fn uniform_random_sampling() {
+        let index_weights: [u32; 4] = [1; 4];
+        let builder = WalkerTableBuilder::new(&index_weights).unwrap();
         let wa_table = builder.build();
 
         let mut rng = rand::thread_rng();
@@ -92,8 +111,8 @@ mod table_test {
 
     #[test]
     fn weighted_random_sampling() {
-        let index_weights = [2, 1, 7, 0];
-        let builder = WalkerTableBuilder::new(&index_weights);
+        let index_weights: [u32; 4] = [2, 1, 7, 0];
+        let builder = WalkerTableBuilder::new(&index_weights).unwrap();
         let wa_table = builder.build();
 
         let idxs = (0..N).map(|_| wa_table.next()).collect::<Vec<usize>>();
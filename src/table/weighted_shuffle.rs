@@ -0,0 +1,166 @@
+//! Weighted sampling without replacement via a binary indexed (Fenwick)
+//! tree.
+
+use rand::rngs::ThreadRng;
+use rand::Rng;
+
+use crate::error::WeightedRandError;
+use crate::weight::AliasableWeight;
+
+/// An unbiased weighted shuffle: an iterator over the indexes of the
+/// weights it was built from, where each index's chance of appearing next
+/// is proportional to its *remaining* weight, and once yielded an index is
+/// never drawn again. An index whose weight is 0 is never yielded.
+///
+/// Unlike [`WalkerTable`](crate::table::WalkerTable), which is built for
+/// i.i.d. draws, this performs sampling *without replacement*, e.g. for a
+/// full weighted shuffle of a slice. It is backed by a binary indexed
+/// (Fenwick) tree over the weights, so both the prefix-sum query used to
+/// pick an index and the update that zeroes it out afterwards are
+/// `O(log n)`.
+pub struct WeightedShuffle<R: Rng> {
+    /// Current remaining weight of each index. A drawn index is set to 0.
+    weights: Vec<f64>,
+
+    /// Fenwick tree of `weights`, 1-indexed (`tree[0]` is unused).
+    tree: Vec<f64>,
+
+    /// Sum of `weights`, kept in sync with `tree` as indexes are drawn.
+    total_remaining: f64,
+
+    /// Number of indexes with nonzero weight that have not yet been drawn.
+    remaining: usize,
+
+    rng: R,
+}
+
+impl WeightedShuffle<ThreadRng> {
+    /// Creates a new [`WeightedShuffle`] over `weights`, drawing indexes
+    /// using the thread-local RNG.
+    ///
+    /// Returns [`WeightedRandError`] if any weight is negative, `NaN`, or
+    /// infinite.
+    pub fn new<T: AliasableWeight>(weights: &[T]) -> Result<Self, WeightedRandError> {
+        Self::new_with_rng(weights, rand::thread_rng())
+    }
+}
+
+impl<R: Rng> WeightedShuffle<R> {
+    /// Creates a new [`WeightedShuffle`] over `weights`, drawing indexes
+    /// using an external RNG which implements Rng.
+    ///
+    /// Returns [`WeightedRandError`] if any weight is negative, `NaN`, or
+    /// infinite.
+    pub fn new_with_rng<T: AliasableWeight>(weights: &[T], rng: R) -> Result<Self, WeightedRandError> {
+        for w in weights {
+            w.validate()?;
+        }
+
+        let n = weights.len();
+        let weights = weights.iter().map(|w| w.as_f64()).collect::<Vec<f64>>();
+
+        let mut tree = vec![0.0; n + 1];
+        for (i, w) in weights.iter().enumerate() {
+            Self::update(&mut tree, i, *w);
+        }
+        let total_remaining = weights.iter().sum();
+        let remaining = weights.iter().filter(|w| **w > 0.0).count();
+
+        Ok(WeightedShuffle {
+            weights,
+            tree,
+            total_remaining,
+            remaining,
+            rng,
+        })
+    }
+
+    /// Adds `delta` to the weight stored at 0-based `index`.
+    fn update(tree: &mut [f64], index: usize, delta: f64) {
+        let mut i = index + 1;
+        while i < tree.len() {
+            tree[i] += delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Finds the 0-based index whose range of the cumulative distribution
+    /// contains `r`, i.e. the smallest index `i` such that the sum of
+    /// `weights[0..=i]` exceeds `r`.
+    ///
+    /// Walks the tree top-down from the highest power of two `<=` the
+    /// number of indexes, descending right (and accumulating the node's
+    /// value) whenever doing so still keeps the accumulated sum `<= r`,
+    /// otherwise descending left. A 0-weight index is never landed on,
+    /// since it never advances the accumulated sum past `r`.
+    fn find_index(&self, r: f64) -> usize {
+        let n = self.weights.len();
+        let mut pos = 0;
+        let mut acc = 0.0;
+
+        let mut bit = match n.checked_next_power_of_two() {
+            Some(p) if p == n => p,
+            Some(p) => p / 2,
+            None => 0,
+        };
+        while bit > 0 {
+            let next = pos + bit;
+            if next <= n && acc + self.tree[next] <= r {
+                pos = next;
+                acc += self.tree[next];
+            }
+            bit /= 2;
+        }
+
+        pos
+    }
+}
+
+impl<R: Rng> Iterator for WeightedShuffle<R> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.remaining == 0 || self.total_remaining <= 0.0 {
+            return None;
+        }
+
+        let r = self.rng.gen_range(0.0..self.total_remaining);
+        let index = self.find_index(r);
+
+        let w = self.weights[index];
+        Self::update(&mut self.tree, index, -w);
+        self.weights[index] = 0.0;
+        self.total_remaining -= w;
+        self.remaining -= 1;
+
+        Some(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+#[cfg(test)]
+mod weighted_shuffle_test {
+    use super::WeightedShuffle;
+
+    #[test]
+    fn yields_every_nonzero_weighted_index_exactly_once() {
+        let weights: [u32; 10] = [2, 7, 9, 2, 4, 8, 1, 3, 6, 5];
+        let mut shuffled = WeightedShuffle::new(&weights).unwrap().collect::<Vec<usize>>();
+        shuffled.sort_unstable();
+
+        assert_eq!(shuffled, (0..weights.len()).collect::<Vec<usize>>())
+    }
+
+    #[test]
+    fn skips_zero_weighted_indexes() {
+        let weights: [u32; 5] = [1, 0, 1, 0, 1];
+        let shuffled = WeightedShuffle::new(&weights).unwrap().collect::<Vec<usize>>();
+
+        assert_eq!(shuffled.len(), 3);
+        assert!(!shuffled.contains(&1));
+        assert!(!shuffled.contains(&3));
+    }
+}
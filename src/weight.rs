@@ -0,0 +1,100 @@
+//! Trait for weight values accepted by [`WalkerTableBuilder`](crate::builder::WalkerTableBuilder).
+
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::error::WeightedRandError;
+
+/// A numeric weight that [`WalkerTableBuilder`](crate::builder::WalkerTableBuilder)
+/// can build an alias table from.
+///
+/// This is modeled on rand's alias-method `Weight` trait. It exposes just
+/// enough arithmetic for the table builder (`Add`, `Sub`, `Mul`, `Div`, a
+/// zero constant, and conversion to `f64`, which the table is built in for
+/// precision), plus a validity check so that negative, `NaN`, or infinite
+/// weights are rejected instead of silently corrupting the table.
+///
+/// It is implemented for `i8`, `u32`, `u64`, `f32`, and `f64`.
+pub trait AliasableWeight:
+    Copy
+    + PartialEq
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    /// The additive identity.
+    const ZERO: Self;
+
+    /// Converts the weight to `f64`, used to build the table in floating
+    /// point.
+    fn as_f64(self) -> f64;
+
+    /// Returns an error if this weight cannot be used to build a table,
+    /// i.e. if it is negative, `NaN`, or infinite.
+    fn validate(self) -> Result<(), WeightedRandError>;
+}
+
+macro_rules! impl_aliasable_weight_unsigned {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl AliasableWeight for $t {
+                const ZERO: Self = 0;
+
+                fn as_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn validate(self) -> Result<(), WeightedRandError> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_aliasable_weight_signed {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl AliasableWeight for $t {
+                const ZERO: Self = 0;
+
+                fn as_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn validate(self) -> Result<(), WeightedRandError> {
+                    if self < 0 {
+                        return Err(WeightedRandError::InvalidWeight);
+                    }
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_aliasable_weight_float {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl AliasableWeight for $t {
+                const ZERO: Self = 0.0;
+
+                fn as_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn validate(self) -> Result<(), WeightedRandError> {
+                    if self.is_nan() || self.is_infinite() || self < 0.0 {
+                        return Err(WeightedRandError::InvalidWeight);
+                    }
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_aliasable_weight_signed!(i8);
+impl_aliasable_weight_unsigned!(u32, u64);
+impl_aliasable_weight_float!(f32, f64);